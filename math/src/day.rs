@@ -0,0 +1,147 @@
+//! Integrating shade over the sun's daily arc
+//!
+//! Someone planning a walk cares less about the instantaneous answer than
+//! about *how long* a spot stays shaded. This module walks solar time across
+//! the daylight arc in fixed increments, asks the [`solar`](crate::solar)
+//! module where the sun is at each step, and reuses the grid shade query to
+//! decide whether the target point is shaded above head height. Summing those
+//! steps gives the shaded minutes and the fraction of daylight spent in shade,
+//! the same day-integration idea the iLand lightroom uses to accumulate light
+//! exposure along the sun's path at a given latitude.
+//!
+//! The grid's `+y` axis is taken to point due south, so the crate's azimuth
+//! convention (measured from south, positive west) drops straight into
+//! [`HeightGrid::shade_height_at`](crate::grid::HeightGrid::shade_height_at).
+
+use crate::grid::HeightGrid;
+use crate::solar::solar_position;
+
+/// How much of a day a point spends in shade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShadeDuration {
+    /// Minutes of daylight during which the point is shaded above head height.
+    pub shaded_minutes: f64,
+    /// Total minutes the sun is above the horizon.
+    pub daylight_minutes: f64,
+    /// `shaded_minutes / daylight_minutes`, or `0.0` on a day with no sun.
+    pub shaded_fraction: f64,
+}
+
+/// Integrate the shaded time at a point over a full day
+///
+/// `latitude` is in degrees and `day_of_year` is `n` as used by the solar
+/// module. The point `(x, y)` is queried against `grid`, and it counts as
+/// shaded whenever the shade reaches at least `person_height`. Solar time is
+/// stepped in `step_minutes` increments from midnight to midnight; only steps
+/// with the sun above the horizon contribute to the totals.
+pub fn shaded_duration<Lat, N, X, Y, H>(
+    latitude: Lat,
+    day_of_year: N,
+    grid: &HeightGrid,
+    x: X,
+    y: Y,
+    person_height: H,
+    step_minutes: u32,
+) -> ShadeDuration
+where
+    Lat: Into<f64>,
+    N: Into<f64>,
+    X: Into<f64>,
+    Y: Into<f64>,
+    H: Into<f64>,
+{
+    let latitude = latitude.into();
+    let day_of_year = day_of_year.into();
+    let x = x.into();
+    let y = y.into();
+    let person_height = person_height.into();
+    let step = step_minutes.max(1);
+
+    let mut daylight_minutes = 0.0;
+    let mut shaded_minutes = 0.0;
+
+    let mut minute = 0u32;
+    while minute < 24 * 60 {
+        let solar_time = minute as f64 / 60.0;
+        let pos = solar_position(latitude, day_of_year, solar_time);
+        if pos.altitude > 0.0 {
+            daylight_minutes += step as f64;
+            if let Some(height) = grid.shade_height_at(x, y, pos.altitude, pos.azimuth) {
+                if height >= person_height {
+                    shaded_minutes += step as f64;
+                }
+            }
+        }
+        minute += step;
+    }
+
+    let shaded_fraction = if daylight_minutes > 0.0 {
+        shaded_minutes / daylight_minutes
+    } else {
+        0.0
+    };
+
+    ShadeDuration {
+        shaded_minutes,
+        daylight_minutes,
+        shaded_fraction,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for the day-long shade integration
+    use super::*;
+
+    /// A strip with a tall building at x=0 and open ground to the west (+x).
+    fn strip() -> HeightGrid {
+        HeightGrid::new(3, 1, 1.0, vec![1000.0, 0.0, 0.0])
+    }
+
+    #[test]
+    fn open_ground_has_some_daylight() {
+        // A summer day at mid-latitude has a non-empty daylight arc.
+        let grid = strip();
+        let summary = shaded_duration(40.0, 172, &grid, 1.5, 0.5, 2.0, 10);
+        assert!(summary.daylight_minutes > 0.0);
+        assert!(summary.shaded_fraction >= 0.0 && summary.shaded_fraction <= 1.0);
+    }
+
+    #[test]
+    fn fraction_is_shaded_over_daylight() {
+        let grid = strip();
+        let summary = shaded_duration(40.0, 172, &grid, 1.5, 0.5, 2.0, 15);
+        assert!(
+            (summary.shaded_fraction - summary.shaded_minutes / summary.daylight_minutes).abs()
+                < 1e-10
+        );
+    }
+
+    #[test]
+    fn noon_shade_lands_on_the_sun_side() {
+        use crate::solar::solar_position;
+
+        // Mid-latitude summer noon: the sun is due south, so +y points toward
+        // it. A building on the +y (south) side shades the point; the same
+        // building on the -y (north) side leaves it in full sun.
+        let noon = solar_position(40.0, 172, 12.0);
+        let to_south = HeightGrid::new(1, 3, 1.0, vec![0.0, 0.0, 1000.0]);
+        let to_north = HeightGrid::new(1, 3, 1.0, vec![1000.0, 0.0, 0.0]);
+        assert!(to_south
+            .shade_height_at(0.5, 1.5, noon.altitude, noon.azimuth)
+            .is_some());
+        assert_eq!(
+            to_north.shade_height_at(0.5, 1.5, noon.altitude, noon.azimuth),
+            None
+        );
+    }
+
+    #[test]
+    fn polar_night_has_no_daylight() {
+        // Far north in deep winter the sun never clears the horizon.
+        let grid = strip();
+        let summary = shaded_duration(80.0, 355, &grid, 1.5, 0.5, 2.0, 30);
+        assert_eq!(summary.daylight_minutes, 0.0);
+        assert_eq!(summary.shaded_fraction, 0.0);
+    }
+}
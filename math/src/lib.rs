@@ -8,6 +8,10 @@
 
 use std::f64;
 
+pub mod day;
+pub mod grid;
+pub mod solar;
+
 /// Calculate the length of a shadow
 ///
 /// In a 2D plane, given a wall and the angle of incidence of the sun,
@@ -89,6 +93,116 @@ where
 }
 
 
+/// An oriented shade projection
+///
+/// Unlike `fully_shaded_len`, which reports a bare horizontal distance, this
+/// carries enough geometry for a caller to place the shaded rectangle on a
+/// map: how deep the safe shade reaches measured straight out from the wall,
+/// and which compass direction the shadow is thrown in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedShade {
+    /// Safe-shade depth measured perpendicular to the wall, in the same units
+    /// as the input heights.
+    pub depth: f64,
+    /// How far the footprint is displaced sideways along the wall, in the same
+    /// units as `depth`; zero when the sun faces the wall head-on.
+    pub lateral: f64,
+    /// Azimuth (radians) the shadow is cast toward, i.e. directly away from
+    /// the sun.
+    pub cast_azimuth: f64,
+}
+
+/// Calculate the safe-shade depth for a wall the sun strikes obliquely
+///
+/// `fully_shaded_len` reports the ground run of the shadow as if the sun
+/// faced the wall head-on. Here we keep that `run` from the sun's `altitude`
+/// but decompose it about the angle Δ between the sun's `sun_azimuth` and the
+/// wall-normal `wall_azimuth`: the ground projection is a vector of length
+/// `run` cast directly away from the sun, so the depth reaching straight out
+/// from the wall is `run · cos(Δ)` (it shrinks toward zero as the sun slides
+/// round to graze the wall edge-on) and the sideways shift along the wall is
+/// `run · sin(Δ)`. Together they place the shaded rectangle.
+///
+/// The returned [`OrientedShade`] also reports the direction the shadow is
+/// cast in — the sun's azimuth plus half a turn.
+pub fn fully_shaded_len_oriented<H1, H2, Alt, SunAz, WallAz>(
+    h1: H1,
+    h2: H2,
+    altitude: Alt,
+    sun_azimuth: SunAz,
+    wall_azimuth: WallAz,
+) -> OrientedShade
+where
+    H1: Into<f64>,
+    H2: Into<f64>,
+    Alt: Into<f64>,
+    SunAz: Into<f64>,
+    WallAz: Into<f64>,
+{
+    let sun_azimuth = sun_azimuth.into();
+    let run = fully_shaded_len(h1, h2, altitude);
+    let delta = sun_azimuth - wall_azimuth.into();
+    // Decompose the run-length ground projection about the wall normal. The
+    // depth shrinks smoothly to 0 as the sun grazes the wall (Δ → 90°); once
+    // the sun slips behind the wall (cos Δ < 0) no shade falls on this face.
+    let depth = (run * delta.cos()).max(0.0);
+    // Sideways shift of the footprint along the wall.
+    let lateral = run * delta.sin();
+    OrientedShade {
+        depth,
+        lateral,
+        cast_azimuth: sun_azimuth + f64::consts::PI,
+    }
+}
+
+
+/// The union of several buildings' shadows along a sightline
+///
+/// `intervals` holds the merged, non-overlapping `[start, end]` stretches of
+/// shade, sorted by `start`; `total` is their combined length. An interval
+/// whose `end` is `INFINITY` (the sun sitting on the horizon) swallows
+/// everything from its `start` onward, so `total` is then `INFINITY` too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedShadows {
+    /// Total shaded length once overlaps are removed.
+    pub total: f64,
+    /// The merged, non-overlapping intervals, sorted by `start`.
+    pub intervals: Vec<(f64, f64)>,
+}
+
+/// Merge overlapping shadow segments cast by several buildings
+///
+/// A street is lined with buildings whose shadows overlap; a walker cares
+/// about how much of the block is shaded, not about double-counting the
+/// overlaps. Each input segment is an `[start, end]` interval of ground
+/// distance produced by one building. We sort the segments by `start` and
+/// sweep, extending the currently open interval whenever the next `start`
+/// falls at or before the current `end`, otherwise closing it off and opening
+/// a fresh one. An `INFINITY` end is absorbing — once one is open, nothing
+/// after it can extend the shade any further.
+pub fn merge_shadows(segments: &[(f64, f64)]) -> MergedShadows {
+    let mut sorted: Vec<(f64, f64)> = segments.to_vec();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut intervals: Vec<(f64, f64)> = Vec::new();
+    for &(start, end) in &sorted {
+        match intervals.last_mut() {
+            Some(open) if start <= open.1 => {
+                // Overlapping (or touching): extend the open interval. Once
+                // an end hits INFINITY it stays there.
+                if end > open.1 {
+                    open.1 = end;
+                }
+            }
+            _ => intervals.push((start, end)),
+        }
+    }
+
+    let total = intervals.iter().map(|&(start, end)| end - start).sum();
+    MergedShadows { total, intervals }
+}
+
+
 /// Convert degrees to radians
 pub fn deg_to_rad<D: Into<f64>>(deg: D) -> f64 {
     deg.into().to_radians()
@@ -186,4 +300,63 @@ mod tests {
         assert_approx(shadow_len, 900);
     }
 
+    #[test]
+    fn oriented_head_on_matches_plain() {
+        // Δ = 0, so cos(Δ) = 1 and the depth is exactly fully_shaded_len.
+        let shade = fully_shaded_len_oriented(1000.0, 100.0, PI / 4.0, 0.0, 0.0);
+        assert_approx(shade.depth, 900);
+    }
+
+    #[test]
+    fn oriented_cast_is_opposite_the_sun() {
+        let shade = fully_shaded_len_oriented(1000.0, 100.0, PI / 4.0, PI / 4.0, 0.0);
+        assert_approx(shade.cast_azimuth, PI / 4.0 + PI);
+    }
+
+    #[test]
+    fn oriented_oblique_sun_shrinks_depth() {
+        // A 60° offset has cos(Δ) = 0.5, so the perpendicular depth halves.
+        let head_on = fully_shaded_len_oriented(1000.0, 100.0, PI / 4.0, 0.0, 0.0);
+        let oblique = fully_shaded_len_oriented(1000.0, 100.0, PI / 4.0, PI / 3.0, 0.0);
+        assert_approx(oblique.depth, head_on.depth * 0.5);
+    }
+
+    #[test]
+    fn oriented_lateral_zero_head_on_nonzero_oblique() {
+        let head_on = fully_shaded_len_oriented(1000.0, 100.0, PI / 4.0, 0.0, 0.0);
+        assert_approx(head_on.lateral, 0.0);
+        let oblique = fully_shaded_len_oriented(1000.0, 100.0, PI / 4.0, PI / 3.0, 0.0);
+        assert!(oblique.lateral > 0.0);
+    }
+
+    #[test]
+    fn merge_disjoint_segments() {
+        let merged = merge_shadows(&[(0.0, 10.0), (20.0, 30.0)]);
+        assert_eq!(merged.intervals, vec![(0.0, 10.0), (20.0, 30.0)]);
+        assert_approx(merged.total, 20);
+    }
+
+    #[test]
+    fn merge_overlapping_segments() {
+        // The second segment starts inside the first, so they fuse.
+        let merged = merge_shadows(&[(0.0, 15.0), (10.0, 25.0)]);
+        assert_eq!(merged.intervals, vec![(0.0, 25.0)]);
+        assert_approx(merged.total, 25);
+    }
+
+    #[test]
+    fn merge_is_order_independent() {
+        let merged = merge_shadows(&[(20.0, 30.0), (0.0, 25.0), (5.0, 10.0)]);
+        assert_eq!(merged.intervals, vec![(0.0, 30.0)]);
+        assert_approx(merged.total, 30);
+    }
+
+    #[test]
+    fn merge_infinite_end_absorbs() {
+        // A shadow reaching to the horizon swallows everything after it.
+        let merged = merge_shadows(&[(0.0, 5.0), (3.0, f64::INFINITY), (100.0, 200.0)]);
+        assert_eq!(merged.intervals, vec![(0.0, f64::INFINITY)]);
+        assert_eq!(merged.total, f64::INFINITY);
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,167 @@
+//! Solar position from latitude, date, and time of day
+//!
+//! `fully_shaded_len` wants the sun's angle of incidence Θ, but a walker
+//! standing on a street corner doesn't know that — they know roughly where
+//! they are, what day it is, and what time it is. This module closes that
+//! gap by computing the sun's altitude (elevation above the horizon) and
+//! azimuth from those everyday inputs, so the altitude can be handed
+//! straight to `fully_shaded_len`/`fully_shaded_area`.
+//!
+//! The recurrence is the standard one from solar-geometry textbooks. With
+//! day-of-year `n`, the solar declination is
+//!
+//! ```text
+//!     δ = 23.45° · sin(360° · (284 + n) / 365)
+//! ```
+//!
+//! the hour angle is `H = 15° · (solar_time − 12)` (so it is zero at solar
+//! noon and swings ±15° for every hour either side), and the altitude α and
+//! azimuth A satisfy
+//!
+//! ```text
+//!     sin(α) = sin(lat)·sin(δ) + cos(lat)·cos(δ)·cos(H)
+//!     cos(A) = (cos(δ)·sin(lat)·cos(H) − sin(δ)·cos(lat)) / cos(α)
+//! ```
+//!
+//! All outputs are `f64` radians, to stay consistent with the rest of the
+//! crate. Azimuth is measured from due south, negative toward the east
+//! (morning) and positive toward the west (afternoon), mirroring the
+//! `solar_pos` (azimuth, elevation) pair the `shadow` R package works from.
+
+use std::f64;
+
+use crate::deg_to_rad;
+
+/// The sun's position in the sky
+///
+/// Both angles are in radians. `altitude` is clamped to `0` once the sun is
+/// at or below the horizon — there the shade is effectively infinite, which
+/// `fully_shaded_len` already reports for a zero incidence angle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolarPosition {
+    /// Elevation of the sun above the horizon, in radians (`0` at/below the
+    /// horizon).
+    pub altitude: f64,
+    /// Azimuth of the sun from due south, in radians; negative east-of-south
+    /// before solar noon, positive west-of-south after.
+    pub azimuth: f64,
+}
+
+/// Solar declination δ (radians) for a given day of the year
+///
+/// `n` is the day-of-year (1 on January 1st). The result swings between
+/// roughly ±23.45° over the course of a year.
+pub fn declination<N: Into<f64>>(day_of_year: N) -> f64 {
+    let n = day_of_year.into();
+    deg_to_rad(23.45 * deg_to_rad(360.0 * (284.0 + n) / 365.0).sin())
+}
+
+/// Hour angle H (radians) for a solar time in hours
+///
+/// Zero at solar noon, `deg_to_rad(15)` per hour afterwards and the negative
+/// of that before.
+pub fn hour_angle<T: Into<f64>>(solar_time: T) -> f64 {
+    deg_to_rad(15.0 * (solar_time.into() - 12.0))
+}
+
+/// Compute the sun's position from latitude, date, and solar time
+///
+/// `latitude` is in degrees (positive north), `day_of_year` is `n` as used
+/// by [`declination`], and `solar_time` is the local solar time in hours
+/// (12.0 = solar noon). The returned altitude feeds directly into
+/// `fully_shaded_len` as Θ.
+pub fn solar_position<Lat, N, T>(latitude: Lat, day_of_year: N, solar_time: T) -> SolarPosition
+where
+    Lat: Into<f64>,
+    N: Into<f64>,
+    T: Into<f64>,
+{
+    let lat = deg_to_rad(latitude);
+    let solar_time = solar_time.into();
+    let dec = declination(day_of_year);
+    let h = hour_angle(solar_time);
+
+    let sin_alt = lat.sin() * dec.sin() + lat.cos() * dec.cos() * h.cos();
+    // Guard against floating-point drift just outside [-1, 1] before asin.
+    let altitude = sin_alt.clamp(-1.0, 1.0).asin().max(0.0);
+
+    // Build the azimuth from the two signed horizontal components of the
+    // sun's direction and take `atan2`, rather than reconstructing a
+    // magnitude from `acos` (which cancels catastrophically near cos = ±1).
+    // Both components carry a common 1/cos(α) factor that `atan2` divides out,
+    // so the altitude clamp at the horizon doesn't affect the result. The
+    // east-west term carries sin(H), negative before noon, so the azimuth is
+    // east-of-south (negative) in the morning and west-of-south after, and it
+    // is exactly 0 at solar noon where H = 0.
+    let east = dec.cos() * h.sin();
+    let south = dec.cos() * lat.sin() * h.cos() - dec.sin() * lat.cos();
+    let azimuth = east.atan2(south);
+
+    SolarPosition { altitude, azimuth }
+}
+
+#[cfg(test)]
+mod tests {
+    //! Tests for the solar-position functions
+    //!
+    //! As elsewhere in the crate, floating-point comparisons assert that the
+    //! absolute difference between numbers is < 1e-10.
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn assert_approx<One, Two>(one: One, two: Two)
+    where
+        One: Into<f64>,
+        Two: Into<f64>,
+    {
+        assert!((one.into() - two.into()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn declination_equinox_is_near_zero() {
+        // The 284 + n term puts n = 81 (late March) within a degree of zero.
+        assert!(declination(81).abs() < deg_to_rad(1.0));
+    }
+
+    #[test]
+    fn hour_angle_noon_is_zero() {
+        assert_approx(hour_angle(12.0), 0.0);
+    }
+
+    #[test]
+    fn hour_angle_one_hour_is_fifteen_degrees() {
+        assert_approx(hour_angle(13.0), deg_to_rad(15.0));
+        assert_approx(hour_angle(11.0), -deg_to_rad(15.0));
+    }
+
+    #[test]
+    fn noon_sun_is_due_south() {
+        // At noon H = 0, so the azimuth collapses to due south (0 radians).
+        let pos = solar_position(40.0, 172, 12.0);
+        assert_approx(pos.azimuth, 0.0);
+    }
+
+    #[test]
+    fn altitude_never_goes_below_horizon() {
+        // Deep winter before sunrise: the raw altitude is negative, but we
+        // clamp it to the horizon.
+        let pos = solar_position(60.0, 355, 4.0);
+        assert_eq!(pos.altitude, 0.0);
+    }
+
+    #[test]
+    fn morning_is_east_afternoon_is_west() {
+        let morning = solar_position(40.0, 172, 9.0);
+        let afternoon = solar_position(40.0, 172, 15.0);
+        assert!(morning.azimuth < 0.0);
+        assert!(afternoon.azimuth > 0.0);
+    }
+
+    #[test]
+    fn altitude_stays_within_a_quarter_turn() {
+        let pos = solar_position(0.0, 81, 12.0);
+        // On the equator at the equinox the noon sun is essentially overhead.
+        assert!(pos.altitude <= PI / 2.0);
+        assert!(pos.altitude > deg_to_rad(89.0));
+    }
+}